@@ -1,6 +1,88 @@
-const COMMANDS: &[&str] = &["ping", "execute"];
+// `src/config.rs` is shared with the build script, so it must only depend on serde (and
+// schemars behind the `schema` feature).
+#[allow(dead_code)]
+#[path = "src/config.rs"]
+mod config;
+
+const COMMANDS: &[&str] = &[
+    "fill",
+    "click",
+    "submit",
+    "submit_and_wait",
+    "clear",
+    "wait_for_url",
+    "wait_for_condition",
+    "wait_until_gone",
+    "query_all",
+    "element_screenshot",
+    "attribute",
+    "property",
+    "text",
+    "send_keys",
+    "perform_actions",
+    "config",
+    "session_id",
+    "session_capabilities",
+    "back",
+    "forward",
+    "refresh",
+    "window_rect",
+    "set_window_rect",
+    "maximize_window",
+    "fullscreen_window",
+    "enter_frame",
+    "enter_frame_by_index",
+    "leave_frame",
+    "self_test",
+    "wait_ready",
+    "cookies_for_url",
+    "set_permission",
+    "local_storage_get",
+    "local_storage_set",
+    "session_storage_get",
+    "session_storage_set",
+    "clear_storage",
+    "add_init_script",
+    "remove_init_script",
+    "upload_file",
+    "accept_alert",
+    "dismiss_alert",
+    "alert_text",
+    "send_alert_text",
+    "dom_snapshot",
+    "close_session",
+    "navigate",
+    "navigate_and_wait",
+    "find_by_text",
+    "find_within",
+    "find_all_within",
+    "browser_version",
+    "driver_version",
+    "reconnect",
+    "batch",
+    "count",
+    "exists",
+    "title",
+    "wait_for_title",
+    "performance_metrics",
+    "status",
+    "open_in_new_tab",
+    "close_tab",
+    "set_clipboard",
+    "get_clipboard",
+    "element_rect",
+    "is_displayed",
+    "export_state",
+    "import_state",
+    "list_profiles",
+    "delete_profile",
+];
 
 fn main() {
+    // Fails the app build with serde's message when its `plugins > fanto` config is invalid.
+    // Instances registered under other names are only checked when the app starts.
+    let _ = tauri_plugin::plugin_config::<config::Config>("fanto");
+
     tauri_plugin::Builder::new(COMMANDS)
         .android_path("android")
         .ios_path("ios")
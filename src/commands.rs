@@ -0,0 +1,617 @@
+use std::{path::PathBuf, time::Duration};
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use tauri::{
+    command,
+    ipc::{CommandArg, CommandItem, InvokeError},
+    Manager, Runtime,
+};
+
+use crate::{
+    fantoccini::Locator, ActionChain, BatchOp, BatchResult, Config, Cookie, ElementInfo, Error,
+    Fanto, PermissionState, Rect, Result, ScriptId, SelfTestReport, SessionStatus, StateBlob,
+    UrlPredicate, WindowRect,
+};
+
+/// The instance a command is for: the one named by the `Fanto-Instance` header, else the
+/// default one.
+pub(crate) struct Instance<R: Runtime>(&'static Fanto<R>);
+
+impl<'de, R: Runtime> CommandArg<'de, R> for Instance<R> {
+    fn from_command(command: CommandItem<'de, R>) -> std::result::Result<Self, InvokeError> {
+        let app = command.message.webview().app_handle().clone();
+        let name = match command.message.headers().get(crate::INSTANCE_HEADER) {
+            Some(name) => Some(
+                name.to_str()
+                    .map_err(|_| Error::InstanceNotFound(format!("{:?}", name)))?,
+            ),
+            None => None,
+        };
+        crate::instance(&app, name).map(Instance).ok_or_else(|| {
+            Error::InstanceNotFound(name.unwrap_or(crate::DEFAULT_NAME).to_string()).into()
+        })
+    }
+}
+
+impl<R: Runtime> std::ops::Deref for Instance<R> {
+    type Target = Fanto<R>;
+
+    fn deref(&self) -> &Fanto<R> {
+        self.0
+    }
+}
+
+// Element commands take a CSS selector and an optional wait timeout in milliseconds.
+
+#[command]
+pub(crate) async fn fill<R: Runtime>(
+    fanto: Instance<R>,
+    selector: String,
+    text: String,
+    timeout: Option<u64>,
+) -> Result<()> {
+    fanto
+        .fill(
+            Locator::Css(&selector),
+            &text,
+            timeout.map(Duration::from_millis),
+        )
+        .await
+}
+
+#[command]
+pub(crate) async fn click<R: Runtime>(
+    fanto: Instance<R>,
+    selector: String,
+    timeout: Option<u64>,
+) -> Result<()> {
+    fanto
+        .click(Locator::Css(&selector), timeout.map(Duration::from_millis))
+        .await
+}
+
+#[command]
+pub(crate) async fn submit<R: Runtime>(
+    fanto: Instance<R>,
+    selector: String,
+    timeout: Option<u64>,
+) -> Result<()> {
+    fanto
+        .submit(Locator::Css(&selector), timeout.map(Duration::from_millis))
+        .await
+}
+
+#[command]
+pub(crate) async fn submit_and_wait<R: Runtime>(
+    fanto: Instance<R>,
+    form: String,
+    wait_for: String,
+    timeout: u64,
+) -> Result<()> {
+    fanto
+        .submit_and_wait(
+            Locator::Css(&form),
+            Locator::Css(&wait_for),
+            Duration::from_millis(timeout),
+        )
+        .await
+}
+
+#[command]
+pub(crate) async fn upload_file<R: Runtime>(
+    fanto: Instance<R>,
+    selector: String,
+    path: PathBuf,
+    timeout: Option<u64>,
+) -> Result<()> {
+    fanto
+        .upload_file(
+            Locator::Css(&selector),
+            &path,
+            timeout.map(Duration::from_millis),
+        )
+        .await
+}
+
+#[command]
+pub(crate) async fn clear<R: Runtime>(
+    fanto: Instance<R>,
+    selector: String,
+    timeout: Option<u64>,
+) -> Result<()> {
+    fanto
+        .clear(Locator::Css(&selector), timeout.map(Duration::from_millis))
+        .await
+}
+
+#[command]
+pub(crate) async fn wait_for_url<R: Runtime>(
+    fanto: Instance<R>,
+    substring: String,
+    timeout: u64,
+) -> Result<String> {
+    let url = fanto
+        .wait_for_url(
+            UrlPredicate::Contains(substring),
+            Duration::from_millis(timeout),
+        )
+        .await?;
+    Ok(url.into())
+}
+
+#[command]
+pub(crate) async fn title<R: Runtime>(fanto: Instance<R>) -> Result<String> {
+    fanto.title().await
+}
+
+#[command]
+pub(crate) async fn wait_for_title<R: Runtime>(
+    fanto: Instance<R>,
+    substring: String,
+    timeout: u64,
+) -> Result<String> {
+    fanto
+        .wait_for_title(
+            UrlPredicate::Contains(substring),
+            Duration::from_millis(timeout),
+        )
+        .await
+}
+
+#[command]
+pub(crate) async fn wait_for_condition<R: Runtime>(
+    fanto: Instance<R>,
+    js: String,
+    timeout: u64,
+) -> Result<serde_json::Value> {
+    fanto
+        .wait_for_condition(&js, Duration::from_millis(timeout))
+        .await
+}
+
+#[command]
+pub(crate) async fn wait_until_gone<R: Runtime>(
+    fanto: Instance<R>,
+    selector: String,
+    timeout: u64,
+) -> Result<()> {
+    fanto
+        .wait_until_gone(Locator::Css(&selector), Duration::from_millis(timeout))
+        .await
+}
+
+#[command]
+pub(crate) async fn query_all<R: Runtime>(
+    fanto: Instance<R>,
+    selector: String,
+) -> Result<Vec<ElementInfo>> {
+    fanto.query_all(Locator::Css(&selector)).await
+}
+
+#[command]
+pub(crate) async fn count<R: Runtime>(fanto: Instance<R>, selector: String) -> Result<usize> {
+    fanto.count(Locator::Css(&selector)).await
+}
+
+#[command]
+pub(crate) async fn exists<R: Runtime>(fanto: Instance<R>, selector: String) -> Result<bool> {
+    fanto.exists(Locator::Css(&selector)).await
+}
+
+#[command]
+pub(crate) async fn find_by_text<R: Runtime>(
+    fanto: Instance<R>,
+    text: String,
+    exact: bool,
+) -> Result<ElementInfo> {
+    let element = fanto.find_by_text(&text, exact).await?;
+    let mut infos = fanto.element_infos(&[element]).await?;
+    Ok(infos.remove(0))
+}
+
+#[command]
+pub(crate) async fn find_within<R: Runtime>(
+    fanto: Instance<R>,
+    parent: String,
+    selector: String,
+) -> Result<ElementInfo> {
+    let parent = fanto.element(&parent).await?;
+    let element = fanto.find_within(&parent, Locator::Css(&selector)).await?;
+    let mut infos = fanto.element_infos(&[element]).await?;
+    Ok(infos.remove(0))
+}
+
+#[command]
+pub(crate) async fn find_all_within<R: Runtime>(
+    fanto: Instance<R>,
+    parent: String,
+    selector: String,
+) -> Result<Vec<ElementInfo>> {
+    let parent = fanto.element(&parent).await?;
+    let elements = fanto
+        .find_all_within(&parent, Locator::Css(&selector))
+        .await?;
+    fanto.element_infos(&elements).await
+}
+
+#[command]
+pub(crate) async fn element_screenshot<R: Runtime>(
+    fanto: Instance<R>,
+    selector: String,
+    timeout: Option<u64>,
+) -> Result<String> {
+    let png = fanto
+        .element_screenshot(Locator::Css(&selector), timeout.map(Duration::from_millis))
+        .await?;
+    Ok(BASE64.encode(png))
+}
+
+#[command]
+pub(crate) async fn element_rect<R: Runtime>(
+    fanto: Instance<R>,
+    selector: String,
+    timeout: Option<u64>,
+) -> Result<Rect> {
+    fanto
+        .element_rect(Locator::Css(&selector), timeout.map(Duration::from_millis))
+        .await
+}
+
+#[command]
+pub(crate) async fn is_displayed<R: Runtime>(
+    fanto: Instance<R>,
+    selector: String,
+    timeout: Option<u64>,
+) -> Result<bool> {
+    fanto
+        .is_displayed(Locator::Css(&selector), timeout.map(Duration::from_millis))
+        .await
+}
+
+#[command]
+pub(crate) async fn attribute<R: Runtime>(
+    fanto: Instance<R>,
+    selector: String,
+    name: String,
+    timeout: Option<u64>,
+) -> Result<Option<String>> {
+    fanto
+        .attribute(
+            Locator::Css(&selector),
+            &name,
+            timeout.map(Duration::from_millis),
+        )
+        .await
+}
+
+#[command]
+pub(crate) async fn property<R: Runtime>(
+    fanto: Instance<R>,
+    selector: String,
+    name: String,
+    timeout: Option<u64>,
+) -> Result<Option<String>> {
+    fanto
+        .property(
+            Locator::Css(&selector),
+            &name,
+            timeout.map(Duration::from_millis),
+        )
+        .await
+}
+
+#[command]
+pub(crate) async fn text<R: Runtime>(
+    fanto: Instance<R>,
+    selector: String,
+    timeout: Option<u64>,
+) -> Result<String> {
+    fanto
+        .text(Locator::Css(&selector), timeout.map(Duration::from_millis))
+        .await
+}
+
+#[command]
+pub(crate) async fn send_keys<R: Runtime>(fanto: Instance<R>, keys: String) -> Result<()> {
+    fanto.send_keys(&keys).await
+}
+
+#[command]
+pub(crate) async fn wait_ready<R: Runtime>(fanto: Instance<R>) -> Result<()> {
+    fanto.wait_ready().await
+}
+
+#[command]
+pub(crate) fn config<R: Runtime>(fanto: Instance<R>) -> Config {
+    fanto.config().clone()
+}
+
+#[command]
+pub(crate) fn browser_version<R: Runtime>(fanto: Instance<R>) -> Option<String> {
+    fanto.browser_version()
+}
+
+#[command]
+pub(crate) fn driver_version<R: Runtime>(fanto: Instance<R>) -> Option<String> {
+    fanto.driver_version()
+}
+
+#[command]
+pub(crate) async fn session_id<R: Runtime>(fanto: Instance<R>) -> Result<Option<String>> {
+    fanto.session_id().await
+}
+
+#[command]
+pub(crate) async fn self_test<R: Runtime>(fanto: Instance<R>) -> Result<SelfTestReport> {
+    fanto.self_test().await
+}
+
+#[command]
+pub(crate) async fn close_session<R: Runtime>(fanto: Instance<R>) -> Result<()> {
+    fanto.close_session().await
+}
+
+#[command]
+pub(crate) async fn reconnect<R: Runtime>(fanto: Instance<R>) -> Result<()> {
+    fanto.reconnect().await
+}
+
+#[command]
+pub(crate) async fn open_in_new_tab<R: Runtime>(fanto: Instance<R>, url: String) -> Result<String> {
+    fanto.open_in_new_tab(&url).await
+}
+
+#[command]
+pub(crate) async fn close_tab<R: Runtime>(fanto: Instance<R>, handle: String) -> Result<()> {
+    fanto.close_tab(&handle).await
+}
+
+#[command]
+pub(crate) async fn navigate<R: Runtime>(fanto: Instance<R>, url: String) -> Result<()> {
+    fanto.navigate(&url).await
+}
+
+#[command]
+pub(crate) async fn navigate_and_wait<R: Runtime>(
+    fanto: Instance<R>,
+    url: String,
+    wait_for: String,
+    timeout: u64,
+) -> Result<()> {
+    fanto
+        .navigate_and_wait(
+            &url,
+            Locator::Css(&wait_for),
+            Duration::from_millis(timeout),
+        )
+        .await
+}
+
+#[command]
+pub(crate) async fn back<R: Runtime>(fanto: Instance<R>) -> Result<()> {
+    fanto.back().await
+}
+
+#[command]
+pub(crate) async fn forward<R: Runtime>(fanto: Instance<R>) -> Result<()> {
+    fanto.forward().await
+}
+
+#[command]
+pub(crate) async fn refresh<R: Runtime>(fanto: Instance<R>) -> Result<()> {
+    fanto.refresh().await
+}
+
+#[command]
+pub(crate) async fn enter_frame<R: Runtime>(
+    fanto: Instance<R>,
+    selector: String,
+    timeout: Option<u64>,
+) -> Result<()> {
+    fanto
+        .enter_frame(Locator::Css(&selector), timeout.map(Duration::from_millis))
+        .await
+}
+
+#[command]
+pub(crate) async fn enter_frame_by_index<R: Runtime>(fanto: Instance<R>, index: u16) -> Result<()> {
+    fanto.enter_frame_by_index(index).await
+}
+
+#[command]
+pub(crate) async fn leave_frame<R: Runtime>(fanto: Instance<R>) -> Result<()> {
+    fanto.leave_frame().await
+}
+
+#[command]
+pub(crate) async fn cookies_for_url<R: Runtime>(
+    fanto: Instance<R>,
+    url: String,
+) -> Result<Vec<Cookie>> {
+    fanto.cookies_for_url(&url).await
+}
+
+#[command]
+pub(crate) async fn export_state<R: Runtime>(
+    fanto: Instance<R>,
+    origins: Vec<String>,
+) -> Result<StateBlob> {
+    let origins = origins.iter().map(String::as_str).collect::<Vec<_>>();
+    fanto.export_state(&origins).await
+}
+
+#[command]
+pub(crate) async fn import_state<R: Runtime>(fanto: Instance<R>, state: StateBlob) -> Result<()> {
+    fanto.import_state(&state).await
+}
+
+#[command]
+pub(crate) async fn set_permission<R: Runtime>(
+    fanto: Instance<R>,
+    name: String,
+    state: PermissionState,
+) -> Result<()> {
+    fanto.set_permission(&name, state).await
+}
+
+#[command]
+pub(crate) async fn local_storage_get<R: Runtime>(
+    fanto: Instance<R>,
+    key: String,
+) -> Result<Option<String>> {
+    fanto.local_storage_get(&key).await
+}
+
+#[command]
+pub(crate) async fn local_storage_set<R: Runtime>(
+    fanto: Instance<R>,
+    key: String,
+    value: String,
+) -> Result<()> {
+    fanto.local_storage_set(&key, &value).await
+}
+
+#[command]
+pub(crate) async fn session_storage_get<R: Runtime>(
+    fanto: Instance<R>,
+    key: String,
+) -> Result<Option<String>> {
+    fanto.session_storage_get(&key).await
+}
+
+#[command]
+pub(crate) async fn session_storage_set<R: Runtime>(
+    fanto: Instance<R>,
+    key: String,
+    value: String,
+) -> Result<()> {
+    fanto.session_storage_set(&key, &value).await
+}
+
+#[command]
+pub(crate) async fn clear_storage<R: Runtime>(fanto: Instance<R>) -> Result<()> {
+    fanto.clear_storage().await
+}
+
+#[command]
+pub(crate) async fn add_init_script<R: Runtime>(
+    fanto: Instance<R>,
+    source: String,
+) -> Result<ScriptId> {
+    fanto.add_init_script(&source).await
+}
+
+#[command]
+pub(crate) async fn remove_init_script<R: Runtime>(fanto: Instance<R>, id: ScriptId) -> Result<()> {
+    fanto.remove_init_script(&id).await
+}
+
+#[command]
+pub(crate) async fn accept_alert<R: Runtime>(fanto: Instance<R>) -> Result<()> {
+    fanto.accept_alert().await
+}
+
+#[command]
+pub(crate) async fn dismiss_alert<R: Runtime>(fanto: Instance<R>) -> Result<()> {
+    fanto.dismiss_alert().await
+}
+
+#[command]
+pub(crate) async fn alert_text<R: Runtime>(fanto: Instance<R>) -> Result<String> {
+    fanto.alert_text().await
+}
+
+#[command]
+pub(crate) async fn send_alert_text<R: Runtime>(fanto: Instance<R>, text: String) -> Result<()> {
+    fanto.send_alert_text(&text).await
+}
+
+#[command]
+pub(crate) async fn dom_snapshot<R: Runtime>(fanto: Instance<R>) -> Result<serde_json::Value> {
+    fanto.dom_snapshot().await
+}
+
+#[command]
+pub(crate) async fn performance_metrics<R: Runtime>(
+    fanto: Instance<R>,
+) -> Result<serde_json::Value> {
+    fanto.performance_metrics().await
+}
+
+#[command]
+pub(crate) async fn set_clipboard<R: Runtime>(fanto: Instance<R>, text: String) -> Result<()> {
+    fanto.set_clipboard(&text).await
+}
+
+#[command]
+pub(crate) async fn get_clipboard<R: Runtime>(fanto: Instance<R>) -> Result<String> {
+    fanto.get_clipboard().await
+}
+
+#[command]
+pub(crate) async fn perform_actions<R: Runtime>(
+    fanto: Instance<R>,
+    actions: serde_json::Value,
+) -> Result<()> {
+    // Parsed here rather than by tauri, so a malformed spec rejects as `invalid_actions`.
+    let chain: ActionChain =
+        serde_json::from_value(actions).map_err(|e| Error::InvalidActions(e.to_string()))?;
+    fanto.perform_actions(&chain).await
+}
+
+#[command]
+pub(crate) async fn batch<R: Runtime>(
+    fanto: Instance<R>,
+    ops: serde_json::Value,
+    fail_fast: Option<bool>,
+) -> Result<Vec<BatchResult>> {
+    // Parsed here rather than by tauri, so a malformed batch rejects as `invalid_batch`.
+    let ops: Vec<BatchOp> =
+        serde_json::from_value(ops).map_err(|e| Error::InvalidBatch(e.to_string()))?;
+    Ok(fanto.batch(&ops, fail_fast.unwrap_or(true)).await)
+}
+
+#[command]
+pub(crate) async fn status<R: Runtime>(fanto: Instance<R>) -> Result<SessionStatus> {
+    fanto.status().await
+}
+
+#[command]
+pub(crate) async fn session_capabilities<R: Runtime>(
+    fanto: Instance<R>,
+) -> Result<serde_json::Value> {
+    fanto.session_capabilities().await
+}
+
+#[command]
+pub(crate) async fn window_rect<R: Runtime>(fanto: Instance<R>) -> Result<WindowRect> {
+    fanto.window_rect().await
+}
+
+#[command]
+pub(crate) async fn set_window_rect<R: Runtime>(
+    fanto: Instance<R>,
+    rect: WindowRect,
+) -> Result<()> {
+    fanto.set_window_rect(rect).await
+}
+
+#[command]
+pub(crate) async fn maximize_window<R: Runtime>(fanto: Instance<R>) -> Result<()> {
+    fanto.maximize_window().await
+}
+
+#[command]
+pub(crate) async fn fullscreen_window<R: Runtime>(fanto: Instance<R>) -> Result<()> {
+    fanto.fullscreen_window().await
+}
+
+#[command]
+pub(crate) async fn list_profiles<R: Runtime>(fanto: Instance<R>) -> Result<Vec<String>> {
+    fanto.list_profiles()
+}
+
+#[command]
+pub(crate) async fn delete_profile<R: Runtime>(fanto: Instance<R>, name: String) -> Result<()> {
+    fanto.delete_profile(&name)
+}
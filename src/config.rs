@@ -0,0 +1,260 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Browser driven by the plugin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum Browser {
+    /// Google Chrome with chromedriver, on every desktop OS.
+    Chrome,
+    /// Microsoft Edge with msedgedriver, on Windows only.
+    Edge,
+}
+
+impl Default for Browser {
+    /// Edge on Windows, Chrome elsewhere.
+    fn default() -> Self {
+        if cfg!(target_os = "windows") {
+            Browser::Edge
+        } else {
+            Browser::Chrome
+        }
+    }
+}
+
+/// When navigation returns, as the W3C `pageLoadStrategy` capability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum PageLoadStrategy {
+    /// Once the page and its resources have loaded, the driver default.
+    Normal,
+    /// Once the DOM is ready, before images and stylesheets.
+    Eager,
+    /// Right away; readiness is up to the caller.
+    None,
+}
+
+/// State the browser window is put in when a session opens.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum WindowState {
+    /// As the browser opens it.
+    #[default]
+    Normal,
+    /// Maximized on the current screen.
+    Maximized,
+    /// Fullscreen, without browser UI.
+    Fullscreen,
+}
+
+/// What to do when `incognito` is on together with a browser profile directory, which some
+/// Chromium versions refuse to start with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum IncognitoUserDataDir {
+    /// Passes both `--incognito` and `--user-data-dir`.
+    #[default]
+    Keep,
+    /// Leaves `--user-data-dir` out while incognito, so the driver picks a temp profile.
+    Drop,
+    /// Fails startup when incognito is combined with a configured `user_data_dir`.
+    Reject,
+}
+
+/// WebDriver session timeouts in milliseconds, in the W3C `timeouts` shape.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct Timeouts {
+    /// Limit for scripts run in the page. Defaults to 60 seconds.
+    pub script: Option<u64>,
+    /// Limit for page loads. Defaults to 60 seconds.
+    pub page_load: Option<u64>,
+    /// How long element lookups keep retrying before failing. Defaults to 15 seconds.
+    pub implicit: Option<u64>,
+}
+
+/// Plugin configuration, read from `plugins > fanto` in `tauri.conf.json`.
+///
+/// Unknown keys are rejected, so typos fail the app build instead of being ignored.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct Config {
+    /// Browser to drive. Defaults to Edge on Windows and Chrome elsewhere.
+    pub browser: Browser,
+    /// Directory holding every plugin file: the driver, its port and the browser profile.
+    ///
+    /// A leading `~` expands to the home directory and relative paths resolve against the
+    /// app local data directory. Created if missing. Defaults to the app local data directory.
+    pub data_dir: Option<PathBuf>,
+    /// File name of the driver inside `data_dir`, e.g. a renamed binary or a wrapper script.
+    ///
+    /// Must be a plain file name. Defaults to `chromedriver`, or `msedgedriver.exe` for Edge,
+    /// with `.exe` on Windows.
+    pub driver_binary_name: Option<String>,
+    /// Browser profile directory.
+    ///
+    /// Expanded like `data_dir`, with relative paths resolving against it. Defaults to
+    /// `driver-user-data` inside it.
+    pub user_data_dir: Option<PathBuf>,
+    /// Opens the browser in incognito (Chrome) or InPrivate (Edge) mode. Defaults to `true`.
+    ///
+    /// Independent of `persist`: extensions that do not load in incognito need it off.
+    pub incognito: Option<bool>,
+    /// How `incognito` combines with the profile directory. Defaults to `keep`.
+    pub incognito_user_data_dir: IncognitoUserDataDir,
+    /// Keeps the browser profile across runs. Defaults to `true`.
+    ///
+    /// When `false`, the profile is a fresh temp dir deleted when the app exits, and
+    /// `user_data_dir` must be unset.
+    pub persist: Option<bool>,
+    /// Browser executable to launch instead of the default install.
+    ///
+    /// Also used to detect the browser version when picking the driver. Expanded like
+    /// `user_data_dir`.
+    pub browser_binary: Option<PathBuf>,
+    /// Browser version to download the driver for, e.g. `"120"`, skipping detection where
+    /// policy blocks it.
+    ///
+    /// Chrome only needs the major version. Edge takes a full version as is and resolves a
+    /// major version to its latest driver.
+    pub browser_version_override: Option<String>,
+    /// Replaces the browser's user agent with `user_agent`.
+    ///
+    /// Off by default, leaving the browser's genuine user agent untouched.
+    pub override_user_agent: bool,
+    /// User agent sent when `override_user_agent` is set.
+    pub user_agent: Option<String>,
+    /// `Accept-Language` header sent with every request, e.g. `"de-DE,de;q=0.9"`.
+    ///
+    /// Only affects content negotiation; the browser UI language is left as is.
+    pub accept_language: Option<String>,
+    /// Base URLs to download the driver from, tried in order until one succeeds.
+    ///
+    /// A mirror must serve the upstream layout below its base URL. Empty uses the upstream
+    /// host only.
+    pub driver_mirrors: Vec<String>,
+    /// Runs the browser without a visible window.
+    pub headless: bool,
+    /// Window state applied to every new session. Anything but `normal` needs a window, so it
+    /// is rejected together with `headless`.
+    pub window_state: WindowState,
+    /// Adds `--disable-gpu`, `--no-sandbox` and `--disable-dev-shm-usage` when `headless` is
+    /// set, which headless Chromium in CI containers usually needs to avoid crashing.
+    pub headless_stability: bool,
+    /// On Linux, starts an `Xvfb` virtual display for a headed browser when neither `DISPLAY`
+    /// nor `WAYLAND_DISPLAY` is set, as in CI. Requires `Xvfb` on the `PATH`.
+    pub auto_xvfb: bool,
+    /// Extra environment variables for the driver process and the browser it launches.
+    ///
+    /// Added on top of the inherited environment, overriding variables of the same name.
+    pub driver_env: HashMap<String, String>,
+    /// Remote IPs allowed to connect to the driver. Empty leaves `--allowed-ips` out, so the
+    /// driver only accepts local connections.
+    pub driver_allowed_ips: Vec<String>,
+    /// IP address the driver is reached at, e.g. `"172.17.0.2"` to expose it to another
+    /// container. A non-loopback address is added to `driver_allowed_ips`. Defaults to
+    /// `localhost`.
+    pub bind_host: Option<String>,
+    /// Host name or IP the plugin connects to the driver at, when it differs from
+    /// `bind_host`, e.g. `host.docker.internal`. Must resolve at startup. Defaults to
+    /// `bind_host`.
+    pub connect_host: Option<String>,
+    /// Origins allowed to send requests to the driver, for remote or grid setups.
+    pub driver_allowed_origins: Vec<String>,
+    /// Extra flags for the driver binary, e.g. `--verbose` or `--log-path=driver.log`.
+    ///
+    /// `--port` is managed by the plugin and rejected here.
+    pub driver_args: Vec<String>,
+    /// Milliseconds the driver may take after launch to answer `/status` before startup
+    /// fails. Defaults to 20 seconds.
+    pub driver_startup_grace_ms: Option<u64>,
+    /// Regex matched against each line the driver prints, e.g.
+    /// `"ChromeDriver was started successfully"`, to tell it is ready without polling
+    /// `/status`.
+    ///
+    /// Falls back to `/status` when no line matches within `driver_startup_grace_ms`.
+    pub driver_ready_log_pattern: Option<String>,
+    /// Milliseconds without any session activity after which the driver is stopped.
+    ///
+    /// It starts again on the next use. Unset keeps the driver running for the app's lifetime.
+    pub idle_timeout: Option<u64>,
+    /// Most browser sessions the driver may run at once. Opening another waits for one to
+    /// close, for up to `session_queue_timeout_ms`. Unset allows any number.
+    pub max_sessions: Option<usize>,
+    /// Milliseconds a new session waits for a free slot under `max_sessions`. Defaults to
+    /// 30 seconds.
+    pub session_queue_timeout_ms: Option<u64>,
+    /// Milliseconds the app waits on exit for the browser session to close. After that the
+    /// driver and browser are killed. Defaults to 5 seconds.
+    pub shutdown_timeout_ms: Option<u64>,
+    /// Cap in bytes on the memory each driver and browser process may allocate, Linux only.
+    ///
+    /// Applied with `setrlimit(RLIMIT_DATA)` to the driver and inherited by the browser it
+    /// launches; a process exceeding it fails to allocate instead of exhausting the machine.
+    pub memory_limit: Option<u64>,
+    /// Largest driver archive to download, in bytes. Defaults to 200 MB.
+    pub max_download_size: Option<u64>,
+    /// When navigation returns. With `none`, use `navigate_and_wait` or `wait_for_url` to
+    /// wait for the page yourself. Unset keeps the driver default, `normal`.
+    pub page_load_strategy: Option<PageLoadStrategy>,
+    /// Hides the "controlled by automated test software" infobar by excluding the
+    /// `enable-automation` switch. Sites can still detect the automation.
+    pub disable_automation_banner: bool,
+    /// Session timeouts, each falling back to its default when unset.
+    pub timeouts: Timeouts,
+    /// Opens the browser session as soon as the driver is up, so the first call does not wait
+    /// for the browser to launch. The browser window then opens at app startup.
+    pub warm_start: bool,
+    /// Attaches to a driver left running by an earlier launch, e.g. after a crash, instead of
+    /// spawning another one next to it.
+    ///
+    /// Only a driver answering `/status` on the saved port with the installed driver's build is
+    /// attached to. The plugin does not own its process, so it is neither watched for crashes
+    /// nor stopped on exit.
+    pub reuse_existing_driver: bool,
+    /// Checks that the driver on the saved port is a ready WebDriver for the configured
+    /// browser, of the installed driver's build, before `reuse_existing_driver` attaches to it.
+    /// A saved port failing the check is discarded. Defaults to `true`.
+    ///
+    /// With `false` any server answering `/status` as ready is attached to.
+    pub validate_cached_port: Option<bool>,
+    /// Starts the driver again after it exits unexpectedly.
+    pub auto_restart: bool,
+    /// Restarts a driver that stopped responding when `driver` or a helper needs it, instead
+    /// of failing. Also checks the shared session before each helper call.
+    pub auto_recover: bool,
+    /// Watches the shared session's window count and emits `window-leak-warning` as it keeps
+    /// growing past its start, to catch flows that leak popups or tabs.
+    pub detect_window_leaks: bool,
+    /// Logs every helper call with its duration and outcome at debug level, through `log`.
+    pub trace_commands: bool,
+}
+
+#[cfg(feature = "schema")]
+impl Config {
+    /// JSON schema of the `plugins > fanto` configuration object.
+    pub fn json_schema() -> schemars::schema::RootSchema {
+        schemars::schema_for!(Config)
+    }
+}
+
+/// Expands a leading `~` to `home` and resolves relative paths against `base`.
+pub(crate) fn expand_path(path: &Path, home: &Path, base: &Path) -> PathBuf {
+    if let Ok(rest) = path.strip_prefix("~") {
+        home.join(rest)
+    } else if path.is_relative() {
+        base.join(path)
+    } else {
+        path.to_path_buf()
+    }
+}
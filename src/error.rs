@@ -1,4 +1,7 @@
-use serde::{ser::Serializer, Serialize};
+use serde::{
+    ser::{SerializeStruct, Serializer},
+    Serialize,
+};
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -18,35 +21,174 @@ pub enum Error {
     #[error(transparent)]
     FantoccniCmdError(#[from] fantoccini::error::CmdError),
 
-    #[cfg(target_os = "macos")]
+    #[cfg(feature = "download")]
     #[error(transparent)]
     WebdriverDownloadError(#[from] webdriver_downloader::prelude::WebdriverDownloadError),
-    #[cfg(target_os = "macos")]
+    #[cfg(feature = "download")]
     #[error(transparent)]
     DefaultPathError(#[from] webdriver_downloader::os_specific::DefaultPathError),
-    #[cfg(target_os = "macos")]
     #[error("Browser is not installed in `{0}`")]
     BrowserNotFound(std::path::PathBuf),
 
     #[error("Error fetching version info: {0}")]
     VersionNotFound(String),
+    #[error("File not found at {0}")]
+    FileNotFound(std::path::PathBuf),
     #[error("Executable not found at {0}")]
     ExecutableNotFound(std::path::PathBuf),
-    #[cfg(target_os = "windows")]
+    #[cfg(feature = "download")]
     #[error(transparent)]
     ReqwestError(#[from] reqwest::Error),
-    #[cfg(target_os = "windows")]
+    #[cfg(feature = "download")]
     #[error(transparent)]
     ZipError(#[from] zip::result::ZipError),
     #[error(transparent)]
     FromUtf8Error(#[from] std::string::FromUtf8Error),
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+    #[error(transparent)]
+    UrlParse(#[from] url::ParseError),
+    #[error("platform not supported")]
+    UnsupportedPlatform,
+    #[error("WebDriver failed to start: {0}")]
+    DriverStartup(String),
+    #[error("Element not found: {0}")]
+    ElementNotFound(String),
+    #[error("Timed out waiting for element: {0}")]
+    ElementTimeout(String),
+    #[error("Browser profile `{0}` is in use, close other browsers using it or set `userDataDir`")]
+    ProfileLocked(std::path::PathBuf),
+    #[error("Invalid profile name `{0}`, it must be a plain directory name")]
+    InvalidProfileName(String),
+    #[error("Data directory `{0}` is not writable, set `dataDir` to a writable location")]
+    DataDirNotWritable(std::path::PathBuf),
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+    #[error("Timed out waiting for navigation, last URL was {0}")]
+    NavigationTimeout(String),
+    #[error("Timed out waiting for condition: {0}")]
+    ConditionTimeout(String),
+    #[error("No dialog is open")]
+    NoAlert,
+    #[error("No browser session is open")]
+    NoSession,
+    #[error("No fanto instance is registered as `{0}`")]
+    InstanceNotFound(String),
+    #[error("Timed out waiting for one of the {0} sessions allowed by `maxSessions` to close")]
+    SessionLimitTimeout(usize),
+    #[error("No display for a headed browser, set `headless`, run under Xvfb or set `autoXvfb`")]
+    NoDisplay,
+    #[error("Web storage is not available on {0}, navigate to a page first")]
+    StorageUnavailable(String),
+    #[error("Clipboard is not available: {0}")]
+    ClipboardUnavailable(String),
+    #[error("Frame not found: {0}")]
+    FrameNotFound(String),
+    #[error("No tab with handle {0}")]
+    TabNotFound(String),
+    #[error("Element is not visible: {0}")]
+    ElementNotVisible(String),
+    #[error("Unsupported: {0}")]
+    Unsupported(String),
+    #[error("Invalid key sequence: {0}")]
+    InvalidKeys(String),
+    #[error("Invalid action: {0}")]
+    InvalidActions(String),
+    #[error("Invalid batch: {0}")]
+    InvalidBatch(String),
+    #[error("Driver download exceeds the {0} byte limit")]
+    DownloadTooLarge(u64),
+    #[error("Driver download failed from {tried:?}: {last}")]
+    DriverDownload {
+        tried: Vec<String>,
+        last: Box<Error>,
+    },
+}
+
+impl Error {
+    /// Returns the snake case name of the variant, e.g. `element_not_found`, for the frontend
+    /// to branch on.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Error::Io(_) => "io",
+            #[cfg(mobile)]
+            Error::PluginInvoke(_) => "plugin_invoke",
+            Error::Tauri(_) => "tauri",
+            Error::FantoccniNewSessionError(_) => "new_session",
+            Error::FantoccniCmdError(_) => "cmd",
+            #[cfg(feature = "download")]
+            Error::WebdriverDownloadError(_) => "webdriver_download",
+            #[cfg(feature = "download")]
+            Error::DefaultPathError(_) => "default_path",
+            Error::BrowserNotFound(_) => "browser_not_found",
+            Error::VersionNotFound(_) => "version_not_found",
+            Error::FileNotFound(_) => "file_not_found",
+            Error::ExecutableNotFound(_) => "executable_not_found",
+            #[cfg(feature = "download")]
+            Error::ReqwestError(_) => "reqwest",
+            #[cfg(feature = "download")]
+            Error::ZipError(_) => "zip",
+            Error::FromUtf8Error(_) => "from_utf8",
+            Error::SerdeJson(_) => "serde_json",
+            Error::UrlParse(_) => "url_parse",
+            Error::UnsupportedPlatform => "unsupported_platform",
+            Error::DriverStartup(_) => "driver_startup",
+            Error::ElementNotFound(_) => "element_not_found",
+            Error::ElementTimeout(_) => "element_timeout",
+            Error::ProfileLocked(_) => "profile_locked",
+            Error::InvalidProfileName(_) => "invalid_profile_name",
+            Error::DataDirNotWritable(_) => "data_dir_not_writable",
+            Error::InvalidConfig(_) => "invalid_config",
+            Error::NavigationTimeout(_) => "navigation_timeout",
+            Error::ConditionTimeout(_) => "condition_timeout",
+            Error::NoAlert => "no_alert",
+            Error::NoSession => "no_session",
+            Error::InstanceNotFound(_) => "instance_not_found",
+            Error::SessionLimitTimeout(_) => "session_limit_timeout",
+            Error::NoDisplay => "no_display",
+            Error::StorageUnavailable(_) => "storage_unavailable",
+            Error::ClipboardUnavailable(_) => "clipboard_unavailable",
+            Error::FrameNotFound(_) => "frame_not_found",
+            Error::TabNotFound(_) => "tab_not_found",
+            Error::ElementNotVisible(_) => "element_not_visible",
+            Error::Unsupported(_) => "unsupported",
+            Error::InvalidKeys(_) => "invalid_keys",
+            Error::InvalidActions(_) => "invalid_actions",
+            Error::InvalidBatch(_) => "invalid_batch",
+            Error::DownloadTooLarge(_) => "download_too_large",
+            Error::DriverDownload { .. } => "driver_download",
+        }
+    }
+
+    /// Returns whether the error is a timeout, whichever operation ran out of time.
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            Error::ElementTimeout(_)
+            | Error::NavigationTimeout(_)
+            | Error::ConditionTimeout(_)
+            | Error::SessionLimitTimeout(_) => true,
+            Error::FantoccniCmdError(fantoccini::error::CmdError::WaitTimeout) => true,
+            Error::Io(e) => e.kind() == std::io::ErrorKind::TimedOut,
+            #[cfg(feature = "download")]
+            Error::ReqwestError(e) => e.is_timeout(),
+            Error::DriverDownload { last, .. } => last.is_timeout(),
+            _ => false,
+        }
+    }
 }
 
+/// Serialized as `{ kind, code, message }`: `kind` from [`Error::kind`], and `code` is
+/// `"timeout"` for timeouts and `null` otherwise, so the frontend can branch without matching
+/// on messages.
 impl Serialize for Error {
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        serializer.serialize_str(self.to_string().as_ref())
+        let mut error = serializer.serialize_struct("Error", 3)?;
+        error.serialize_field("kind", self.kind())?;
+        error.serialize_field("code", &self.is_timeout().then_some("timeout"))?;
+        error.serialize_field("message", &self.to_string())?;
+        error.end()
     }
 }
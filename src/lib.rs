@@ -1,3 +1,8 @@
+use std::{
+    collections::HashMap,
+    sync::{PoisonError, RwLock},
+};
+
 use tauri::{
     plugin::{Builder, TauriPlugin},
     Manager, RunEvent, Runtime,
@@ -7,10 +12,23 @@ use tauri::{
 mod desktop;
 #[cfg(mobile)]
 mod mobile;
+#[cfg(desktop)]
+mod tab;
 
+mod commands;
+mod config;
 mod error;
+mod models;
+#[cfg(desktop)]
+mod provider;
 
+pub use config::{Browser, Config, IncognitoUserDataDir, PageLoadStrategy, Timeouts, WindowState};
 pub use error::{Error, Result};
+pub use models::*;
+#[cfg(desktop)]
+pub use provider::{BoxFuture, DriverProvider};
+#[cfg(desktop)]
+pub use tab::Tab;
 
 pub use fantoccini;
 
@@ -19,35 +37,219 @@ use desktop::Fanto;
 #[cfg(mobile)]
 use mobile::Fanto;
 
+/// Name the plugin registers under unless [`FantoBuilder::name`] sets another.
+const DEFAULT_NAME: &str = "fanto";
+
+/// Header the frontend sets to address an instance registered under another name.
+const INSTANCE_HEADER: &str = "Fanto-Instance";
+
+/// The registered instances by name.
+///
+/// An instance lives as long as the app, so it is leaked on registration and handed out as a
+/// plain reference.
+struct Instances<R: Runtime>(RwLock<HashMap<&'static str, &'static Fanto<R>>>);
+
+/// Returns the instance registered as `name`, or with `None` the default one: the instance
+/// under the default name, else the only instance.
+fn instance<R: Runtime>(
+    manager: &impl Manager<R>,
+    name: Option<&str>,
+) -> Option<&'static Fanto<R>> {
+    let instances = manager.try_state::<Instances<R>>()?;
+    let instances = instances
+        .inner()
+        .0
+        .read()
+        .unwrap_or_else(PoisonError::into_inner);
+    match name {
+        Some(name) => instances.get(name).copied(),
+        None => match instances.get(DEFAULT_NAME) {
+            Some(fanto) => Some(*fanto),
+            None if instances.len() == 1 => instances.values().next().copied(),
+            None => None,
+        },
+    }
+}
+
 /// Extensions to [`tauri::App`], [`tauri::AppHandle`] and [`tauri::Window`] to access the fanto APIs.
 pub trait FantoExt<R: Runtime> {
+    /// Returns the instance registered under the default name, or the only instance when it
+    /// was renamed.
+    ///
+    /// # Panics
+    ///
+    /// When no instance qualifies; use [`fanto_named`](Self::fanto_named) with several
+    /// renamed instances.
     fn fanto(&self) -> &Fanto<R>;
+
+    /// Returns the instance registered as `name` with [`FantoBuilder::name`].
+    fn fanto_named(&self, name: &str) -> Option<&Fanto<R>>;
 }
 
 impl<R: Runtime, T: Manager<R>> crate::FantoExt<R> for T {
     fn fanto(&self) -> &Fanto<R> {
-        self.state::<Fanto<R>>().inner()
+        instance(self, None).expect("no fanto instance under the default name")
+    }
+
+    fn fanto_named(&self, name: &str) -> Option<&Fanto<R>> {
+        instance(self, Some(name))
     }
 }
 
 /// Initializes the plugin.
-pub fn init<R: Runtime>() -> TauriPlugin<R> {
-    Builder::new("fanto")
-        .setup(|app, api| {
-            #[cfg(mobile)]
-            let fanto = mobile::init(app, api)?;
+pub fn init<R: Runtime>() -> TauriPlugin<R, Option<Config>> {
+    FantoBuilder::new().build()
+}
+
+/// Builds the plugin with non-default settings.
+pub struct FantoBuilder {
+    name: &'static str,
+    #[cfg(desktop)]
+    provider: Option<std::sync::Arc<dyn DriverProvider>>,
+}
+
+impl Default for FantoBuilder {
+    fn default() -> Self {
+        FantoBuilder {
+            name: DEFAULT_NAME,
             #[cfg(desktop)]
-            let fanto = desktop::Fanto::init(app, api)?;
-            app.manage(fanto);
-            Ok(())
-        })
-        .on_event(|app, event| {
-            if let RunEvent::Exit = event {
-                if let Some(fanto) = app.try_state::<Fanto<R>>() {
-                    #[cfg(desktop)]
-                    fanto.destroy();
-                };
-            }
-        })
-        .build()
+            provider: None,
+        }
+    }
+}
+
+impl FantoBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the plugin as `name` instead of `fanto`, e.g. to run several instances with
+    /// their own driver and browser next to each other.
+    ///
+    /// The configuration is read from `plugins > <name>` and events are emitted as
+    /// `<name>://...`. Reach the instance with [`FantoExt::fanto_named`]. The frontend keeps
+    /// calling `plugin:fanto|...`, since permissions only exist under that name, and picks the
+    /// instance with `useInstance`; that needs an instance under the default name too.
+    pub fn name(mut self, name: &'static str) -> Self {
+        self.name = name;
+        self
+    }
+
+    /// Installs the driver with `provider` instead of the built-in download for
+    /// `Config::browser`.
+    #[cfg(desktop)]
+    pub fn driver_provider(mut self, provider: impl DriverProvider + 'static) -> Self {
+        self.provider = Some(std::sync::Arc::new(provider));
+        self
+    }
+
+    pub fn build<R: Runtime>(self) -> TauriPlugin<R, Option<Config>> {
+        let name = self.name;
+        #[cfg(desktop)]
+        let provider = self.provider;
+        Builder::<R, Option<Config>>::new(name)
+            .invoke_handler(tauri::generate_handler![
+                commands::fill,
+                commands::click,
+                commands::submit,
+                commands::submit_and_wait,
+                commands::clear,
+                commands::wait_for_url,
+                commands::wait_for_condition,
+                commands::wait_until_gone,
+                commands::query_all,
+                commands::element_screenshot,
+                commands::attribute,
+                commands::property,
+                commands::text,
+                commands::send_keys,
+                commands::perform_actions,
+                commands::config,
+                commands::session_id,
+                commands::session_capabilities,
+                commands::back,
+                commands::forward,
+                commands::refresh,
+                commands::window_rect,
+                commands::set_window_rect,
+                commands::maximize_window,
+                commands::fullscreen_window,
+                commands::enter_frame,
+                commands::enter_frame_by_index,
+                commands::leave_frame,
+                commands::self_test,
+                commands::wait_ready,
+                commands::cookies_for_url,
+                commands::set_permission,
+                commands::local_storage_get,
+                commands::local_storage_set,
+                commands::session_storage_get,
+                commands::session_storage_set,
+                commands::clear_storage,
+                commands::add_init_script,
+                commands::remove_init_script,
+                commands::upload_file,
+                commands::accept_alert,
+                commands::dismiss_alert,
+                commands::alert_text,
+                commands::send_alert_text,
+                commands::dom_snapshot,
+                commands::close_session,
+                commands::navigate,
+                commands::navigate_and_wait,
+                commands::find_by_text,
+                commands::find_within,
+                commands::find_all_within,
+                commands::browser_version,
+                commands::driver_version,
+                commands::reconnect,
+                commands::batch,
+                commands::count,
+                commands::exists,
+                commands::title,
+                commands::wait_for_title,
+                commands::performance_metrics,
+                commands::status,
+                commands::open_in_new_tab,
+                commands::close_tab,
+                commands::set_clipboard,
+                commands::get_clipboard,
+                commands::element_rect,
+                commands::is_displayed,
+                commands::export_state,
+                commands::import_state,
+                commands::list_profiles,
+                commands::delete_profile,
+            ])
+            .setup(move |app, api| {
+                if app.fanto_named(name).is_some() {
+                    return Err(format!("fanto is already registered as `{}`", name).into());
+                }
+                #[cfg(mobile)]
+                let fanto = mobile::init(app, api)?;
+                #[cfg(desktop)]
+                let fanto = desktop::Fanto::init(app, api, name, provider)?;
+                let fanto: &'static Fanto<R> = Box::leak(Box::new(fanto));
+                app.manage(Instances::<R>(Default::default()));
+                app.state::<Instances<R>>()
+                    .0
+                    .write()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .insert(name, fanto);
+                #[cfg(desktop)]
+                fanto.start();
+                Ok(())
+            })
+            .on_event(move |app, event| {
+                if let RunEvent::Exit = event {
+                    if let Some(fanto) = app.fanto_named(name) {
+                        #[cfg(desktop)]
+                        if let Err(e) = fanto.destroy() {
+                            log::error!("failed to stop the webdriver: {}", e);
+                        }
+                    };
+                }
+            })
+            .build()
+    }
 }
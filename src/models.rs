@@ -0,0 +1,387 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Condition a URL must satisfy, used by `Fanto::wait_for_url`, or a page title by
+/// `Fanto::wait_for_title`.
+#[derive(Debug, Clone)]
+pub enum UrlPredicate {
+    /// The URL contains the given substring.
+    Contains(String),
+    /// The URL matches the given regular expression.
+    Matches(regex::Regex),
+    /// The URL is exactly the given string.
+    Equals(String),
+}
+
+impl UrlPredicate {
+    /// Returns whether `url` satisfies the predicate.
+    pub fn is_match(&self, url: &str) -> bool {
+        match self {
+            UrlPredicate::Contains(s) => url.contains(s.as_str()),
+            UrlPredicate::Matches(re) => re.is_match(url),
+            UrlPredicate::Equals(s) => url == s,
+        }
+    }
+}
+
+/// Basic information about an element, as returned by `Fanto::query_all`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElementInfo {
+    /// WebDriver reference of the element, valid until the page changes.
+    #[serde(default)]
+    pub id: String,
+    /// Rendered text of the element.
+    pub text: String,
+    /// Outer HTML of the element.
+    pub html: String,
+    /// Attributes of the element by name.
+    pub attributes: HashMap<String, String>,
+}
+
+/// Browser sessions of the driver, as returned by `Fanto::status`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionStatus {
+    /// Sessions the driver is running, `0` while it is not up.
+    pub active: usize,
+    /// Sessions waiting for a slot under `max_sessions`.
+    pub queued: usize,
+    /// `max_sessions`, if set.
+    pub max_sessions: Option<usize>,
+}
+
+/// Payload of the `fanto://driver-crashed` event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriverCrashed {
+    /// Exit code of the driver process, if it exited normally.
+    pub code: Option<i32>,
+}
+
+/// Payload of the `fanto://driver-updated` event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DriverUpdated {
+    /// Browser version the driver was fetched for.
+    pub browser_version: Option<String>,
+    /// Version reported by the new driver binary.
+    pub driver_version: Option<String>,
+}
+
+/// Payload of the `fanto://window-leak-warning` event.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct WindowLeakWarning {
+    /// Windows and tabs open when the session started.
+    pub baseline: usize,
+    /// Windows and tabs open now.
+    pub count: usize,
+}
+
+/// Position and size of the browser window, in CSS pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowRect {
+    /// Distance of the window's left edge from the primary screen's, negative on a screen to
+    /// its left.
+    pub x: i32,
+    /// Distance of the window's top edge from the primary screen's, negative on a screen above
+    /// it.
+    pub y: i32,
+    /// Outer width of the window.
+    pub width: u32,
+    /// Outer height of the window.
+    pub height: u32,
+}
+
+/// Position and size of an element relative to the page, in CSS pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Rect {
+    /// Distance of the element's left edge from the document's.
+    pub x: f64,
+    /// Distance of the element's top edge from the document's.
+    pub y: f64,
+    /// Width of the element's border box.
+    pub width: f64,
+    /// Height of the element's border box.
+    pub height: f64,
+}
+
+/// Outcome of `Fanto::self_test`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelfTestReport {
+    /// Whether every step that ran succeeded.
+    pub passed: bool,
+    /// Steps in the order they ran.
+    pub steps: Vec<SelfTestStep>,
+}
+
+/// One step of `Fanto::self_test`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfTestStep {
+    /// `driver`, `session`, `navigate`, `screenshot` or `close`.
+    pub name: String,
+    /// Whether the step succeeded.
+    pub ok: bool,
+    /// Time the step took, in milliseconds.
+    pub duration_ms: u64,
+    /// Error message of a failed step.
+    pub error: Option<String>,
+}
+
+/// A browser cookie, as returned by `Fanto::cookies_for_url`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Cookie {
+    /// Name of the cookie.
+    pub name: String,
+    /// Value of the cookie.
+    pub value: String,
+    /// Domain the cookie is sent to.
+    pub domain: String,
+    /// Path the cookie is scoped to.
+    pub path: String,
+    /// Expiry as seconds since the Unix epoch, `-1` for session cookies.
+    pub expires: f64,
+    /// Whether scripts are denied access to the cookie.
+    pub http_only: bool,
+    /// Whether the cookie is only sent over HTTPS.
+    pub secure: bool,
+    /// Whether the cookie lives only as long as the browser session.
+    pub session: bool,
+    /// `Strict`, `Lax` or `None`, if the cookie sets it.
+    #[serde(default)]
+    pub same_site: Option<String>,
+}
+
+/// Login state of a set of origins, from `Fanto::export_state` for `Fanto::import_state`.
+///
+/// Holds cookies and `localStorage` only; `sessionStorage`, IndexedDB, service workers and
+/// cache storage are not captured.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateBlob {
+    /// Cookies whose domain matches one of the origins' hosts, of any path.
+    pub cookies: Vec<Cookie>,
+    /// `localStorage` items keyed by origin, e.g. `https://example.com`.
+    pub local_storage: HashMap<String, HashMap<String, String>>,
+}
+
+/// Answer given to a browser permission request, used by `Fanto::set_permission`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PermissionState {
+    /// Allowed without asking.
+    Granted,
+    /// Refused without asking.
+    Denied,
+    /// Asks the user, the browser default.
+    Prompt,
+}
+
+/// Identifier of a script registered with `Fanto::add_init_script`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ScriptId(pub String);
+
+/// One step of an [`ActionChain`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Action {
+    /// Presses `key`, a single character or a key name such as `Shift` or `Enter`, as in
+    /// `Fanto::send_keys`'s `{Name}` tokens.
+    KeyDown { key: String },
+    /// Releases `key`.
+    KeyUp { key: String },
+    /// Presses mouse `button`: `0` left, `1` middle, `2` right.
+    PointerDown {
+        #[serde(default)]
+        button: u64,
+    },
+    /// Releases mouse `button`.
+    PointerUp {
+        #[serde(default)]
+        button: u64,
+    },
+    /// Moves the mouse to `x`, `y` from the center of the element matched by the CSS
+    /// `selector`, or from the top-left corner of the viewport without one.
+    PointerMove {
+        #[serde(default)]
+        selector: Option<String>,
+        #[serde(default)]
+        x: i64,
+        #[serde(default)]
+        y: i64,
+        /// Milliseconds the move takes, instant when unset.
+        #[serde(default)]
+        duration: Option<u64>,
+    },
+    /// Moves the mouse by `x`, `y` from where it is.
+    PointerMoveBy {
+        x: i64,
+        y: i64,
+        #[serde(default)]
+        duration: Option<u64>,
+    },
+    /// Waits `duration` milliseconds.
+    Pause { duration: u64 },
+}
+
+/// Keyboard and mouse steps run one after another by `Fanto::perform_actions`.
+///
+/// Serialized as the plain list of its actions.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ActionChain {
+    /// Steps in the order they run.
+    pub actions: Vec<Action>,
+}
+
+impl ActionChain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Presses `key` without releasing it.
+    pub fn key_down(mut self, key: &str) -> Self {
+        self.actions.push(Action::KeyDown { key: key.into() });
+        self
+    }
+
+    /// Releases `key`.
+    pub fn key_up(mut self, key: &str) -> Self {
+        self.actions.push(Action::KeyUp { key: key.into() });
+        self
+    }
+
+    /// Presses the left mouse button without releasing it.
+    pub fn press(mut self) -> Self {
+        self.actions.push(Action::PointerDown { button: 0 });
+        self
+    }
+
+    /// Releases the left mouse button.
+    pub fn release(mut self) -> Self {
+        self.actions.push(Action::PointerUp { button: 0 });
+        self
+    }
+
+    /// Presses and releases the left mouse button.
+    pub fn click(self) -> Self {
+        self.press().release()
+    }
+
+    /// Moves the mouse to the center of the element matched by the CSS `selector`.
+    pub fn move_to(mut self, selector: &str) -> Self {
+        self.actions.push(Action::PointerMove {
+            selector: Some(selector.into()),
+            x: 0,
+            y: 0,
+            duration: None,
+        });
+        self
+    }
+
+    /// Moves the mouse to `x`, `y` in the viewport.
+    pub fn move_to_point(mut self, x: i64, y: i64) -> Self {
+        self.actions.push(Action::PointerMove {
+            selector: None,
+            x,
+            y,
+            duration: None,
+        });
+        self
+    }
+
+    /// Moves the mouse by `x`, `y` from where it is.
+    pub fn move_by(mut self, x: i64, y: i64) -> Self {
+        self.actions.push(Action::PointerMoveBy {
+            x,
+            y,
+            duration: None,
+        });
+        self
+    }
+
+    /// Waits before the next step.
+    pub fn pause(mut self, duration: std::time::Duration) -> Self {
+        self.actions.push(Action::Pause {
+            duration: duration.as_millis() as u64,
+        });
+        self
+    }
+}
+
+/// One operation of `Fanto::batch`, serialized as `{ action, args }`.
+///
+/// Selectors are CSS selectors and timeouts are in milliseconds, as in the element commands.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", content = "args", rename_all = "camelCase")]
+pub enum BatchOp {
+    Navigate {
+        url: String,
+    },
+    Back,
+    Forward,
+    Refresh,
+    WaitFor {
+        selector: String,
+        timeout: u64,
+    },
+    Click {
+        selector: String,
+        #[serde(default)]
+        timeout: Option<u64>,
+    },
+    Fill {
+        selector: String,
+        text: String,
+        #[serde(default)]
+        timeout: Option<u64>,
+    },
+    Clear {
+        selector: String,
+        #[serde(default)]
+        timeout: Option<u64>,
+    },
+    Submit {
+        selector: String,
+        #[serde(default)]
+        timeout: Option<u64>,
+    },
+    SendKeys {
+        keys: String,
+    },
+    /// Returns the rendered text of the element.
+    Text {
+        selector: String,
+        #[serde(default)]
+        timeout: Option<u64>,
+    },
+    /// Returns the attribute `name`, `null` when absent.
+    Attribute {
+        selector: String,
+        name: String,
+        #[serde(default)]
+        timeout: Option<u64>,
+    },
+    /// Returns the property `name`, `null` when absent.
+    Property {
+        selector: String,
+        name: String,
+        #[serde(default)]
+        timeout: Option<u64>,
+    },
+    /// Returns an [`ElementInfo`] for every match, without waiting.
+    QueryAll {
+        selector: String,
+    },
+}
+
+/// Outcome of one [`BatchOp`]: its `value`, `null` for operations without one, or the
+/// `error` it failed with.
+#[derive(Debug, Serialize)]
+pub struct BatchResult {
+    pub ok: bool,
+    pub value: serde_json::Value,
+    pub error: Option<crate::Error>,
+}
@@ -0,0 +1,24 @@
+use std::{future::Future, path::Path, pin::Pin};
+
+use crate::Result;
+
+/// Future returned by [`DriverProvider::ensure`].
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Installs the driver for one browser.
+///
+/// The plugin picks a built-in provider from `Config::browser`; supply your own with
+/// [`FantoBuilder::driver_provider`](crate::FantoBuilder::driver_provider), e.g. to fetch
+/// drivers from an internal store.
+pub trait DriverProvider: Send + Sync {
+    /// Returns the version of the installed browser, e.g. `120.0.6099.109`.
+    ///
+    /// Asked after [`ensure`](Self::ensure) returns `None`; an error leaves the version unknown.
+    fn detect_browser_version(&self) -> Result<String>;
+
+    /// Makes sure a driver matching the browser is at `driver_path`, downloading it if needed.
+    ///
+    /// Returns the browser version the driver matches, when known. Called each time the
+    /// driver is launched, so it should return quickly when the driver is already in place.
+    fn ensure<'a>(&'a self, driver_path: &'a Path) -> BoxFuture<'a, Result<Option<String>>>;
+}
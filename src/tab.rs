@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use fantoccini::{elements::Element, wd::WindowHandle, Client, Locator};
+use tauri::async_runtime::Mutex;
+
+use crate::error::Result;
+
+/// A browser tab opened by `Fanto::new_tab`.
+///
+/// Every call switches to the tab, runs, and switches back to the tab that was active
+/// before, so tabs can be used side by side. Dropping a `Tab` leaves it open; call
+/// [`Tab::close`] to close it.
+pub struct Tab {
+    client: Client,
+    handle: WindowHandle,
+    switch: Arc<Mutex<()>>,
+}
+
+impl Tab {
+    pub(crate) fn new(client: Client, handle: WindowHandle, switch: Arc<Mutex<()>>) -> Self {
+        Tab {
+            client,
+            handle,
+            switch,
+        }
+    }
+
+    /// Returns the window handle of the tab.
+    pub fn handle(&self) -> &WindowHandle {
+        &self.handle
+    }
+
+    /// Navigates the tab to `url`.
+    pub async fn goto(&self, url: &str) -> Result<()> {
+        let _switch = self.switch.lock().await;
+        let previous = self.enter().await?;
+        let result = self.client.goto(url).await;
+        self.leave(previous).await?;
+        Ok(result?)
+    }
+
+    /// Returns the URL of the tab.
+    pub async fn current_url(&self) -> Result<url::Url> {
+        let _switch = self.switch.lock().await;
+        let previous = self.enter().await?;
+        let result = self.client.current_url().await;
+        self.leave(previous).await?;
+        Ok(result?)
+    }
+
+    /// Finds the element matching `locator` in the tab.
+    ///
+    /// The element can only be used while its tab is active, e.g. through [`Tab::execute`].
+    pub async fn find(&self, locator: Locator<'_>) -> Result<Element> {
+        let _switch = self.switch.lock().await;
+        let previous = self.enter().await?;
+        let result = self.client.find(locator).await;
+        self.leave(previous).await?;
+        Ok(result?)
+    }
+
+    /// Runs `script` in the tab, like `Client::execute`.
+    pub async fn execute(
+        &self,
+        script: &str,
+        args: Vec<serde_json::Value>,
+    ) -> Result<serde_json::Value> {
+        let _switch = self.switch.lock().await;
+        let previous = self.enter().await?;
+        let result = self.client.execute(script, args).await;
+        self.leave(previous).await?;
+        Ok(result?)
+    }
+
+    /// Closes the tab and returns to the tab that was active before.
+    pub async fn close(self) -> Result<()> {
+        let _switch = self.switch.lock().await;
+        let previous = self.enter().await?;
+        self.client.close_window().await?;
+        if previous != self.handle {
+            self.client.switch_to_window(previous).await?;
+        }
+        Ok(())
+    }
+
+    /// Switches to the tab, returning the handle that was active.
+    async fn enter(&self) -> Result<WindowHandle> {
+        let previous = self.client.window().await?;
+        if previous != self.handle {
+            self.client.switch_to_window(self.handle.clone()).await?;
+        }
+        Ok(previous)
+    }
+
+    async fn leave(&self, previous: WindowHandle) -> Result<()> {
+        if previous != self.handle {
+            self.client.switch_to_window(previous).await?;
+        }
+        Ok(())
+    }
+}
@@ -1,5 +1,635 @@
-import { invoke } from '@tauri-apps/api/core'
+import { invoke, type InvokeArgs } from '@tauri-apps/api/core'
 
-export async function execute() {
-  await invoke('plugin:fanto|execute')
+let instance: string | null = null
+
+/**
+ * Sends the calls that follow to the instance registered as `name` with `FantoBuilder::name`,
+ * or back to the default instance with `null`. Calls go through the `fanto` plugin either way,
+ * so its permissions apply.
+ */
+export function useInstance(name: string | null): void {
+  instance = name
+}
+
+async function call<T>(command: string, args?: InvokeArgs): Promise<T> {
+  const headers: Record<string, string> = instance ? { 'Fanto-Instance': instance } : {}
+  return await invoke<T>(`plugin:fanto|${command}`, args, { headers })
+}
+
+// Element commands take a CSS selector and an optional wait timeout in milliseconds.
+
+/** Basic information about an element, as returned by `queryAll`. */
+export interface ElementInfo {
+  /** WebDriver reference of the element, for `findWithin` and `findAllWithin`. */
+  id: string
+  /** Rendered text of the element. */
+  text: string
+  /** Outer HTML of the element. */
+  html: string
+  /** Attributes of the element by name. */
+  attributes: Record<string, string>
+}
+
+/** Rejection value of every command. */
+export interface FantoError {
+  /** Snake case name of the Rust `Error` variant, e.g. `element_not_found`. */
+  kind: string
+  /** `timeout` when an operation ran out of time, else `null`. */
+  code: 'timeout' | null
+  message: string
+}
+
+/** Browser driven by the plugin. */
+export type Browser = 'chrome' | 'edge'
+
+/** When navigation returns. */
+export type PageLoadStrategy = 'normal' | 'eager' | 'none'
+
+export type WindowState = 'normal' | 'maximized' | 'fullscreen'
+
+/** How `incognito` combines with the browser profile directory. */
+export type IncognitoUserDataDir = 'keep' | 'drop' | 'reject'
+
+/** WebDriver session timeouts in milliseconds. */
+export interface Timeouts {
+  script: number | null
+  pageLoad: number | null
+  implicit: number | null
+}
+
+/** Effective plugin configuration, as returned by `config`. */
+export interface Config {
+  browser: Browser
+  dataDir: string | null
+  driverBinaryName: string | null
+  userDataDir: string | null
+  incognito: boolean | null
+  incognitoUserDataDir: IncognitoUserDataDir
+  persist: boolean | null
+  browserBinary: string | null
+  browserVersionOverride: string | null
+  overrideUserAgent: boolean
+  userAgent: string | null
+  acceptLanguage: string | null
+  driverMirrors: string[]
+  headless: boolean
+  windowState: WindowState
+  headlessStability: boolean
+  autoXvfb: boolean
+  driverEnv: Record<string, string>
+  driverAllowedIps: string[]
+  bindHost: string | null
+  connectHost: string | null
+  driverAllowedOrigins: string[]
+  driverArgs: string[]
+  driverStartupGraceMs: number | null
+  driverReadyLogPattern: string | null
+  idleTimeout: number | null
+  maxSessions: number | null
+  sessionQueueTimeoutMs: number | null
+  shutdownTimeoutMs: number | null
+  memoryLimit: number | null
+  maxDownloadSize: number | null
+  pageLoadStrategy: PageLoadStrategy | null
+  disableAutomationBanner: boolean
+  timeouts: Timeouts
+  warmStart: boolean
+  reuseExistingDriver: boolean
+  validateCachedPort: boolean | null
+  autoRestart: boolean
+  autoRecover: boolean
+  detectWindowLeaks: boolean
+  traceCommands: boolean
+}
+
+/** Position and size of an element relative to the page, in CSS pixels. */
+export interface Rect {
+  x: number
+  y: number
+  width: number
+  height: number
+}
+
+/**
+ * Position and size of the browser window, in CSS pixels. `x` and `y` are negative on a screen
+ * left of or above the primary one.
+ */
+export interface WindowRect {
+  x: number
+  y: number
+  width: number
+  height: number
+}
+
+/**
+ * One step of `performActions`. Keys are a single character or a name such as `Shift`;
+ * buttons are `0` left, `1` middle and `2` right; durations are in milliseconds.
+ */
+export type Action =
+  | { type: 'keyDown'; key: string }
+  | { type: 'keyUp'; key: string }
+  | { type: 'pointerDown'; button?: number }
+  | { type: 'pointerUp'; button?: number }
+  | { type: 'pointerMove'; selector?: string; x?: number; y?: number; duration?: number }
+  | { type: 'pointerMoveBy'; x: number; y: number; duration?: number }
+  | { type: 'pause'; duration: number }
+
+/**
+ * One operation of `batch`. Selectors are CSS selectors and timeouts are in milliseconds;
+ * `text`, `attribute`, `property` and `queryAll` produce a value, the others `null`.
+ */
+export type BatchOp =
+  | { action: 'navigate'; args: { url: string } }
+  | { action: 'back' }
+  | { action: 'forward' }
+  | { action: 'refresh' }
+  | { action: 'waitFor'; args: { selector: string; timeout: number } }
+  | { action: 'click'; args: { selector: string; timeout?: number } }
+  | { action: 'fill'; args: { selector: string; text: string; timeout?: number } }
+  | { action: 'clear'; args: { selector: string; timeout?: number } }
+  | { action: 'submit'; args: { selector: string; timeout?: number } }
+  | { action: 'sendKeys'; args: { keys: string } }
+  | { action: 'text'; args: { selector: string; timeout?: number } }
+  | { action: 'attribute'; args: { selector: string; name: string; timeout?: number } }
+  | { action: 'property'; args: { selector: string; name: string; timeout?: number } }
+  | { action: 'queryAll'; args: { selector: string } }
+
+/** Outcome of one `BatchOp`. */
+export interface BatchResult {
+  ok: boolean
+  value: unknown
+  error: FantoError | null
+}
+
+/** Outcome of `selfTest`. */
+export interface SelfTestReport {
+  /** Whether every step that ran succeeded. */
+  passed: boolean
+  steps: SelfTestStep[]
+}
+
+/** One step of `selfTest`: `driver`, `session`, `navigate`, `screenshot` or `close`. */
+export interface SelfTestStep {
+  name: string
+  ok: boolean
+  durationMs: number
+  error: string | null
+}
+
+/** A browser cookie, as returned by `cookiesForUrl`. */
+export interface Cookie {
+  name: string
+  value: string
+  domain: string
+  path: string
+  /** Seconds since the Unix epoch, `-1` for session cookies. */
+  expires: number
+  httpOnly: boolean
+  secure: boolean
+  session: boolean
+  sameSite: 'Strict' | 'Lax' | 'None' | null
+}
+
+/** Cookies and `localStorage` of a set of origins, from `exportState`. */
+export interface StateBlob {
+  cookies: Cookie[]
+  /** Items keyed by origin, e.g. `https://example.com`. */
+  localStorage: Record<string, Record<string, string>>
+}
+
+/** Answer given to a browser permission request. */
+export type PermissionState = 'granted' | 'denied' | 'prompt'
+
+/** Browser sessions of the driver, as returned by `status`. */
+export interface SessionStatus {
+  /** Sessions the driver is running, `0` while it is not up. */
+  active: number
+  /** Sessions waiting for a slot under `maxSessions`. */
+  queued: number
+  maxSessions: number | null
+}
+
+/** Payload of the `fanto://driver-crashed` event. */
+export interface DriverCrashed {
+  /** Exit code of the driver process, `null` when it was killed by a signal. */
+  code: number | null
+}
+
+/** Payload of the `fanto://driver-updated` event. */
+export interface DriverUpdated {
+  /** Browser version the driver was fetched for. */
+  browserVersion: string | null
+  /** Version reported by the new driver binary. */
+  driverVersion: string | null
+}
+
+/** Payload of the `fanto://window-leak-warning` event. */
+export interface WindowLeakWarning {
+  /** Windows and tabs open when the session started. */
+  baseline: number
+  /** Windows and tabs open now. */
+  count: number
+}
+
+export async function fill(
+  selector: string,
+  text: string,
+  timeout?: number
+): Promise<void> {
+  await call('fill', { selector, text, timeout })
+}
+
+export async function click(selector: string, timeout?: number): Promise<void> {
+  await call('click', { selector, timeout })
+}
+
+export async function submit(selector: string, timeout?: number): Promise<void> {
+  await call('submit', { selector, timeout })
+}
+
+/** Submits the form `form`, then waits for `waitFor` to appear. */
+export async function submitAndWait(
+  form: string,
+  waitFor: string,
+  timeout: number
+): Promise<void> {
+  await call('submit_and_wait', { form, waitFor, timeout })
+}
+
+/** Selects the file at `path` in the file input matched by `selector`. */
+export async function uploadFile(
+  selector: string,
+  path: string,
+  timeout?: number
+): Promise<void> {
+  await call('upload_file', { selector, path, timeout })
+}
+
+export async function clear(selector: string, timeout?: number): Promise<void> {
+  await call('clear', { selector, timeout })
+}
+
+/** Waits until the URL contains `substring`, resolving to the matching URL. */
+export async function waitForUrl(
+  substring: string,
+  timeout: number
+): Promise<string> {
+  return await call('wait_for_url', { substring, timeout })
+}
+
+export async function title(): Promise<string> {
+  return await call('title')
+}
+
+/** Waits until the page title contains `substring`, resolving to the matching title. */
+export async function waitForTitle(
+  substring: string,
+  timeout: number
+): Promise<string> {
+  return await call('wait_for_title', { substring, timeout })
+}
+
+/**
+ * Waits until the JavaScript expression `js`, evaluated in the page, is truthy, e.g.
+ * `'window.myApp?.ready === true'`. Resolves to its value.
+ */
+export async function waitForCondition(js: string, timeout: number): Promise<unknown> {
+  return await call('wait_for_condition', { js, timeout })
+}
+
+/** Waits until no displayed element matches `selector`. */
+export async function waitUntilGone(
+  selector: string,
+  timeout: number
+): Promise<void> {
+  await call('wait_until_gone', { selector, timeout })
+}
+
+export async function queryAll(selector: string): Promise<ElementInfo[]> {
+  return await call('query_all', { selector })
+}
+
+/** Returns how many elements match `selector` right now, without waiting. */
+export async function count(selector: string): Promise<number> {
+  return await call('count', { selector })
+}
+
+/** Returns whether any element matches `selector` right now, without waiting. */
+export async function exists(selector: string): Promise<boolean> {
+  return await call('exists', { selector })
+}
+
+/** Resolves to the first element whose own text contains `text`, or equals it when `exact`. */
+export async function findByText(text: string, exact = false): Promise<ElementInfo> {
+  return await call('find_by_text', { text, exact })
+}
+
+/** Finds the first match of `selector` inside the element with id `parent`. */
+export async function findWithin(
+  parent: string,
+  selector: string
+): Promise<ElementInfo> {
+  return await call('find_within', { parent, selector })
+}
+
+/** Finds every match of `selector` inside the element with id `parent`. */
+export async function findAllWithin(
+  parent: string,
+  selector: string
+): Promise<ElementInfo[]> {
+  return await call('find_all_within', { parent, selector })
+}
+
+/** Resolves to the PNG screenshot of the element, base64 encoded. */
+export async function elementScreenshot(
+  selector: string,
+  timeout?: number
+): Promise<string> {
+  return await call('element_screenshot', { selector, timeout })
+}
+
+export async function elementRect(
+  selector: string,
+  timeout?: number
+): Promise<Rect> {
+  return await call('element_rect', { selector, timeout })
+}
+
+/** Resolves to `false` for elements hidden by CSS instead of rejecting. */
+export async function isDisplayed(
+  selector: string,
+  timeout?: number
+): Promise<boolean> {
+  return await call('is_displayed', { selector, timeout })
+}
+
+export async function attribute(
+  selector: string,
+  name: string,
+  timeout?: number
+): Promise<string | null> {
+  return await call('attribute', { selector, name, timeout })
+}
+
+export async function property(
+  selector: string,
+  name: string,
+  timeout?: number
+): Promise<string | null> {
+  return await call('property', { selector, name, timeout })
+}
+
+export async function text(selector: string, timeout?: number): Promise<string> {
+  return await call('text', { selector, timeout })
+}
+
+/** Presses keys on the focused element, e.g. `{Ctrl}a` or `{Escape}`. */
+export async function sendKeys(keys: string): Promise<void> {
+  await call('send_keys', { keys })
+}
+
+/**
+ * Runs `actions` back to back, e.g. a drag and drop:
+ * `[{ type: 'pointerMove', selector: '#card' }, { type: 'pointerDown' },
+ * { type: 'pointerMove', selector: '#done' }, { type: 'pointerUp' }]`.
+ */
+export async function performActions(actions: Action[]): Promise<void> {
+  await call('perform_actions', { actions })
+}
+
+/**
+ * Runs `ops` one after another in a single call, returning the outcome of each. With
+ * `failFast`, the default, it stops after the first failure.
+ */
+export async function batch(ops: BatchOp[], failFast?: boolean): Promise<BatchResult[]> {
+  return await call('batch', { ops, failFast })
+}
+
+/** Returns how many sessions the driver runs and how many wait under `maxSessions`. */
+export async function status(): Promise<SessionStatus> {
+  return await call('status')
+}
+
+/** Resolves once the driver is up, rejecting if it failed to start. */
+export async function waitReady(): Promise<void> {
+  await call('wait_ready')
+}
+
+/** Resolves to the effective configuration, with passwords in `driverEnv` masked. */
+export async function config(): Promise<Config> {
+  return await call('config')
+}
+
+/** Browser version detected when the driver was launched, `null` if unknown. */
+export async function browserVersion(): Promise<string | null> {
+  return await call('browser_version')
+}
+
+/** Version reported by the driver binary, `null` if unknown. */
+export async function driverVersion(): Promise<string | null> {
+  return await call('driver_version')
+}
+
+export async function sessionId(): Promise<string | null> {
+  return await call('session_id')
+}
+
+/** Resolves to the capabilities the driver accepted; rejects when no session is open. */
+export async function sessionCapabilities(): Promise<Record<string, unknown>> {
+  return await call('session_capabilities')
+}
+
+/** Runs the automation pipeline once, e.g. behind a "test automation" button. */
+export async function selfTest(): Promise<SelfTestReport> {
+  return await call('self_test')
+}
+
+/** Closes the browser session but keeps the driver running for a quick next session. */
+export async function closeSession(): Promise<void> {
+  await call('close_session')
+}
+
+/** Replaces the browser session with a fresh one, e.g. after the driver restarted. */
+export async function reconnect(): Promise<void> {
+  await call('reconnect')
+}
+
+/**
+ * Opens `url` in a new tab and makes it the active tab, resolving to its window handle for
+ * `closeTab`.
+ */
+export async function openInNewTab(url: string): Promise<string> {
+  return await call('open_in_new_tab', { url })
+}
+
+/** Closes the tab with window handle `handle`, activating another tab if it was active. */
+export async function closeTab(handle: string): Promise<void> {
+  await call('close_tab', { handle })
+}
+
+export async function navigate(url: string): Promise<void> {
+  await call('navigate', { url })
+}
+
+/** Navigates to `url`, then waits for `waitFor` to appear. */
+export async function navigateAndWait(
+  url: string,
+  waitFor: string,
+  timeout: number
+): Promise<void> {
+  await call('navigate_and_wait', { url, waitFor, timeout })
+}
+
+export async function back(): Promise<void> {
+  await call('back')
+}
+
+export async function forward(): Promise<void> {
+  await call('forward')
+}
+
+export async function refresh(): Promise<void> {
+  await call('refresh')
+}
+
+/** Resolves to the cookies the browser would send to `url`, without navigating. */
+export async function cookiesForUrl(url: string): Promise<Cookie[]> {
+  return await call('cookies_for_url', { url })
+}
+
+/**
+ * Resolves to the cookies and `localStorage` of `origins`, e.g. `['https://example.com']`,
+ * as plain JSON to store and pass to `importState` later.
+ */
+export async function exportState(origins: string[]): Promise<StateBlob> {
+  return await call('export_state', { origins })
+}
+
+export async function importState(state: StateBlob): Promise<void> {
+  await call('import_state', { state })
+}
+
+/** Answers permission `name`, e.g. `camera` or `notifications`, for every origin. */
+export async function setPermission(
+  name: string,
+  state: PermissionState
+): Promise<void> {
+  await call('set_permission', { name, state })
+}
+
+/** Resolves to the `localStorage` value of `key` on the current page, or `null`. */
+export async function localStorageGet(key: string): Promise<string | null> {
+  return await call('local_storage_get', { key })
+}
+
+export async function localStorageSet(key: string, value: string): Promise<void> {
+  await call('local_storage_set', { key, value })
+}
+
+export async function sessionStorageGet(key: string): Promise<string | null> {
+  return await call('session_storage_get', { key })
+}
+
+export async function sessionStorageSet(key: string, value: string): Promise<void> {
+  await call('session_storage_set', { key, value })
+}
+
+/** Clears `localStorage` and `sessionStorage` of the current page's origin. */
+export async function clearStorage(): Promise<void> {
+  await call('clear_storage')
+}
+
+/**
+ * Runs `source` before any page script in every new document of the current tab.
+ * Resolves to an id for `removeInitScript`.
+ */
+export async function addInitScript(source: string): Promise<string> {
+  return await call('add_init_script', { source })
+}
+
+export async function removeInitScript(id: string): Promise<void> {
+  await call('remove_init_script', { id })
+}
+
+/** Accepts the open `alert`, `confirm` or `prompt` dialog. */
+export async function acceptAlert(): Promise<void> {
+  await call('accept_alert')
+}
+
+export async function dismissAlert(): Promise<void> {
+  await call('dismiss_alert')
+}
+
+export async function alertText(): Promise<string> {
+  return await call('alert_text')
+}
+
+/** Types into the open `prompt` dialog; confirm with `acceptAlert`. */
+export async function sendAlertText(text: string): Promise<void> {
+  await call('send_alert_text', { text })
+}
+
+/** Resolves to the page structure from CDP `DOMSnapshot.captureSnapshot`. */
+export async function domSnapshot(): Promise<unknown> {
+  return await call('dom_snapshot')
+}
+
+/**
+ * Writes `text` to the system clipboard from the page. Needs a secure context, such as
+ * `https` or `localhost`, with the browser window focused.
+ */
+export async function setClipboard(text: string): Promise<void> {
+  await call('set_clipboard', { text })
+}
+
+/** Reads the text on the system clipboard, under the same conditions as `setClipboard`. */
+export async function getClipboard(): Promise<string> {
+  return await call('get_clipboard')
+}
+
+/**
+ * Resolves to the page's performance counters by name, e.g. `JSHeapUsedSize` or
+ * `TaskDuration`, from CDP `Performance.getMetrics`.
+ */
+export async function performanceMetrics(): Promise<Record<string, number>> {
+  return await call('performance_metrics')
+}
+
+export async function windowRect(): Promise<WindowRect> {
+  return await call('window_rect')
+}
+
+export async function setWindowRect(rect: WindowRect): Promise<void> {
+  await call('set_window_rect', { rect })
+}
+
+export async function maximizeWindow(): Promise<void> {
+  await call('maximize_window')
+}
+
+export async function fullscreenWindow(): Promise<void> {
+  await call('fullscreen_window')
+}
+
+/** Switches into the frame matched by `selector` until `leaveFrame`. */
+export async function enterFrame(selector: string, timeout?: number): Promise<void> {
+  await call('enter_frame', { selector, timeout })
+}
+
+export async function enterFrameByIndex(index: number): Promise<void> {
+  await call('enter_frame_by_index', { index })
+}
+
+export async function leaveFrame(): Promise<void> {
+  await call('leave_frame')
+}
+
+/** Resolves to the names of the profiles created by `session_with_profile`. */
+export async function listProfiles(): Promise<string[]> {
+  return await call('list_profiles')
+}
+
+/** Deletes the named profile and its logins. Close its sessions first. */
+export async function deleteProfile(name: string): Promise<void> {
+  await call('delete_profile', { name })
 }